@@ -0,0 +1,71 @@
+use core::fmt;
+
+use crate::{escape_to, unescape_to};
+
+/// A [`Display`](fmt::Display) adapter that escapes `&'a str` directly into a formatter, without
+/// allocating an intermediate [`String`].
+///
+/// ```rust
+/// use hescape::Escape;
+///
+/// assert_eq!(format!("<p>{}</p>", Escape("<script>")), "<p>&lt;script&gt;</p>");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Escape<'a>(pub &'a str);
+
+impl fmt::Display for Escape<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        escape_to(f, self.0)
+    }
+}
+
+/// A [`Display`](fmt::Display) adapter that unescapes `&'a str` directly into a formatter,
+/// without allocating an intermediate [`String`].
+///
+/// ```rust
+/// use hescape::Unescape;
+///
+/// assert_eq!(format!("{}", Unescape("&lt;script&gt;")), "<script>");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unescape<'a>(pub &'a str);
+
+impl fmt::Display for Unescape<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        unescape_to(f, self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_display() {
+        assert_eq!(format!("{}", Escape("<script>")), "&lt;script&gt;");
+    }
+
+    #[test]
+    fn test_escape_display_no_special_chars() {
+        assert_eq!(format!("{}", Escape("hello")), "hello");
+    }
+
+    #[test]
+    fn test_unescape_display() {
+        assert_eq!(format!("{}", Unescape("&lt;script&gt;")), "<script>");
+    }
+
+    #[test]
+    fn test_unescape_display_no_references() {
+        assert_eq!(format!("{}", Unescape("hello")), "hello");
+    }
+
+    #[test]
+    fn test_write_into_buffer() {
+        use fmt::Write;
+
+        let mut buffer = String::new();
+        write!(buffer, "<p>{}</p>", Escape("Tom & Jerry")).unwrap();
+        assert_eq!(buffer, "<p>Tom &amp; Jerry</p>");
+    }
+}
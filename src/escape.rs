@@ -1,10 +1,45 @@
 use core::fmt;
+use std::borrow::Cow;
+
+/// Controls which characters are escaped by [`escape_with`]/[`escape_to_with`].
+///
+/// The variants mirror the HTML5 serialization rules for the grammatical position a string is
+/// written into: escaping more than the context requires is safe but wasteful, while escaping
+/// less than it requires is unsound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EscapeMode {
+    /// Escapes `&`, `<`, `>`, `"`, and `'`. Safe for any context, and the mode used by [`escape`]
+    /// and [`escape_to`].
+    #[default]
+    Default,
+    /// Escapes only `&`, `<`, and `>`. Suitable for text nodes.
+    Text,
+    /// Escapes `&`, `<`, `>`, and `"`. Suitable for double-quoted attribute values.
+    Attr,
+    /// Escapes `&`, `<`, `>`, and `'`. Suitable for single-quoted attribute values.
+    SingleQuoteAttr,
+}
+
+impl EscapeMode {
+    /// Returns the escaped replacement for `byte` in this mode, or `None` if `byte` does not need
+    /// escaping.
+    fn replacement(self, byte: u8) -> Option<&'static str> {
+        match byte {
+            b'&' => Some("&amp;"),
+            b'<' => Some("&lt;"),
+            b'>' => Some("&gt;"),
+            b'"' if matches!(self, EscapeMode::Default | EscapeMode::Attr) => Some("&quot;"),
+            b'\'' if matches!(self, EscapeMode::Default | EscapeMode::SingleQuoteAttr) => {
+                Some("&#39;")
+            }
+            _ => None,
+        }
+    }
+}
 
 /// Escapes a HTML string and returns the escaped string.
 pub fn escape(input: &str) -> String {
-    let mut output = String::with_capacity(input.len());
-    escape_to(&mut output, input).unwrap();
-    output
+    escape_with(input, EscapeMode::Default)
 }
 
 /// Escapes a HTML string into a writer.
@@ -12,39 +47,73 @@ pub fn escape_to<W>(writer: &mut W, input: &str) -> fmt::Result
 where
     W: fmt::Write + ?Sized,
 {
-    // Fast path for strings without special characters
-    if !input
-        .bytes()
-        .any(|b| matches!(b, b'&' | b'<' | b'>' | b'"' | b'\''))
-    {
-        writer.write_str(input)?;
-        return Ok(());
+    escape_to_with(writer, input, EscapeMode::Default)
+}
+
+/// Escapes a HTML string for the given [`EscapeMode`] and returns the escaped string.
+pub fn escape_with(input: &str, mode: EscapeMode) -> String {
+    let mut output = String::with_capacity(input.len());
+    escape_to_with(&mut output, input, mode).unwrap();
+    output
+}
+
+/// Options controlling [`escape_with_options`]/[`escape_to_with_options`], beyond the core
+/// [`EscapeMode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EscapeOptions {
+    /// Which characters are escaped, as in [`escape_with`].
+    pub mode: EscapeMode,
+    /// When `true`, every non-ASCII scalar value is emitted as a hexadecimal numeric character
+    /// reference (e.g. `&#xe9;`) instead of being passed through as UTF-8. Useful when the output
+    /// must be restricted to ASCII, such as for an ASCII-only transport or a legacy parser.
+    pub numeric_entities: bool,
+}
+
+impl EscapeOptions {
+    /// Creates options for `mode` with numeric-entity escaping disabled.
+    pub fn new(mode: EscapeMode) -> Self {
+        Self {
+            mode,
+            numeric_entities: false,
+        }
+    }
+
+    /// Enables numeric-entity escaping for non-ASCII scalar values.
+    pub fn with_numeric_entities(mut self) -> Self {
+        self.numeric_entities = true;
+        self
+    }
+}
+
+/// Escapes a HTML string, borrowing `input` unchanged if it contains no characters that need
+/// escaping, and allocating an owned, escaped string otherwise.
+pub fn escape_cow(input: &str) -> Cow<'_, str> {
+    if next_special(input.as_bytes(), 0, EscapeMode::Default).is_none() {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(escape(input))
     }
+}
 
+/// Escapes a HTML string for the given [`EscapeMode`] into a writer.
+pub fn escape_to_with<W>(writer: &mut W, input: &str, mode: EscapeMode) -> fmt::Result
+where
+    W: fmt::Write + ?Sized,
+{
     let bytes = input.as_bytes();
     let mut last = 0usize;
-    let mut i = 0usize;
-
-    while i < bytes.len() {
-        let replacement = match bytes[i] {
-            b'&' => "&amp;",
-            b'<' => "&lt;",
-            b'>' => "&gt;",
-            b'"' => "&quot;",
-            b'\'' => "&#39;",
-            _ => {
-                i += 1;
-                continue;
-            }
-        };
+
+    while let Some(i) = next_special(bytes, last, mode) {
+        let replacement = mode
+            .replacement(bytes[i])
+            .expect("next_special only returns the index of a byte that needs escaping");
 
         if last < i {
             writer.write_str(&input[last..i])?;
         }
         writer.write_str(replacement)?;
 
-        i += 1;
-        last = i;
+        last = i + 1;
     }
 
     if last < input.len() {
@@ -54,6 +123,99 @@ where
     Ok(())
 }
 
+/// Escapes a HTML string for the given [`EscapeOptions`] and returns the escaped string.
+pub fn escape_with_options(input: &str, options: EscapeOptions) -> String {
+    let mut output = String::with_capacity(input.len());
+    escape_to_with_options(&mut output, input, options).unwrap();
+    output
+}
+
+/// Escapes a HTML string for the given [`EscapeOptions`] into a writer.
+///
+/// When `options.numeric_entities` is `false`, this is identical to `escape_to_with(writer,
+/// input, options.mode)`.
+pub fn escape_to_with_options<W>(writer: &mut W, input: &str, options: EscapeOptions) -> fmt::Result
+where
+    W: fmt::Write + ?Sized,
+{
+    if !options.numeric_entities {
+        return escape_to_with(writer, input, options.mode);
+    }
+
+    let mut last = 0usize;
+
+    for (idx, c) in input.char_indices() {
+        if c.is_ascii() {
+            if let Some(replacement) = options.mode.replacement(c as u8) {
+                if last < idx {
+                    writer.write_str(&input[last..idx])?;
+                }
+                writer.write_str(replacement)?;
+                last = idx + 1;
+            }
+        } else {
+            if last < idx {
+                writer.write_str(&input[last..idx])?;
+            }
+            write!(writer, "&#x{:x};", c as u32)?;
+            last = idx + c.len_utf8();
+        }
+    }
+
+    if last < input.len() {
+        writer.write_str(&input[last..])?;
+    }
+
+    Ok(())
+}
+
+/// Returns the index of the next byte at or after `start` that needs escaping in `mode`, or
+/// `None` if no such byte remains.
+#[cfg(not(feature = "memchr"))]
+fn next_special(bytes: &[u8], start: usize, mode: EscapeMode) -> Option<usize> {
+    scalar_next_special(bytes, start, mode)
+}
+
+/// Returns the index of the next byte at or after `start` that needs escaping in `mode`, or
+/// `None` if no such byte remains.
+#[cfg(feature = "memchr")]
+fn next_special(bytes: &[u8], start: usize, mode: EscapeMode) -> Option<usize> {
+    simd_next_special(bytes, start, mode)
+}
+
+/// Byte-by-byte scalar scan for the next special byte. This is the fallback used when the
+/// `memchr` feature is disabled, and is kept available under the feature too so its output can be
+/// checked against [`simd_next_special`].
+#[cfg(any(not(feature = "memchr"), test))]
+fn scalar_next_special(bytes: &[u8], start: usize, mode: EscapeMode) -> Option<usize> {
+    bytes[start..]
+        .iter()
+        .position(|&b| mode.replacement(b).is_some())
+        .map(|pos| start + pos)
+}
+
+/// Jumps directly to the next special byte using `memchr`'s multi-needle SIMD search instead of
+/// inspecting every byte, so the intervening clean run can be copied with a single `write_str`
+/// call.
+#[cfg(feature = "memchr")]
+fn simd_next_special(bytes: &[u8], start: usize, mode: EscapeMode) -> Option<usize> {
+    let haystack = &bytes[start..];
+
+    let common = memchr::memchr3(b'&', b'<', b'>', haystack);
+    let quote = match mode {
+        EscapeMode::Default => memchr::memchr2(b'"', b'\'', haystack),
+        EscapeMode::Attr => memchr::memchr(b'"', haystack),
+        EscapeMode::SingleQuoteAttr => memchr::memchr(b'\'', haystack),
+        EscapeMode::Text => None,
+    };
+
+    match (common, quote) {
+        (Some(a), Some(b)) => Some(start + a.min(b)),
+        (Some(a), None) | (None, Some(a)) => Some(start + a),
+        (None, None) => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +307,113 @@ mod tests {
             "&lt;script&gt;alert(&quot;xss&quot;)&lt;/script&gt;"
         );
     }
+
+    #[test]
+    fn test_text_mode_leaves_quotes_alone() {
+        assert_eq!(
+            escape_with("<a> & \"it's\"", EscapeMode::Text),
+            "&lt;a&gt; &amp; \"it's\""
+        );
+    }
+
+    #[test]
+    fn test_attr_mode_escapes_double_quote_only() {
+        assert_eq!(
+            escape_with("<a> & \"it's\"", EscapeMode::Attr),
+            "&lt;a&gt; &amp; &quot;it's&quot;"
+        );
+    }
+
+    #[test]
+    fn test_single_quote_attr_mode_escapes_single_quote_only() {
+        assert_eq!(
+            escape_with("<a> & \"it's\"", EscapeMode::SingleQuoteAttr),
+            "&lt;a&gt; &amp; \"it&#39;s\""
+        );
+    }
+
+    #[test]
+    fn test_default_mode_matches_escape() {
+        assert_eq!(
+            escape_with("<a> & \"it's\"", EscapeMode::Default),
+            escape("<a> & \"it's\"")
+        );
+    }
+
+    #[test]
+    fn test_escape_cow_borrows_clean_input() {
+        assert!(matches!(escape_cow("hello world"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_escape_cow_owns_escaped_input() {
+        match escape_cow("<div>") {
+            Cow::Owned(s) => assert_eq!(s, "&lt;div&gt;"),
+            Cow::Borrowed(_) => panic!("expected an owned string"),
+        }
+    }
+
+    #[test]
+    fn test_numeric_entities_ascii_only_unchanged() {
+        let options = EscapeOptions::new(EscapeMode::Default).with_numeric_entities();
+        assert_eq!(
+            escape_with_options("<div>", options),
+            escape_with("<div>", EscapeMode::Default)
+        );
+    }
+
+    #[test]
+    fn test_numeric_entities_encodes_non_ascii() {
+        let options = EscapeOptions::new(EscapeMode::Text).with_numeric_entities();
+        assert_eq!(escape_with_options("café", options), "caf&#xe9;");
+    }
+
+    #[test]
+    fn test_numeric_entities_encodes_c1_control_characters() {
+        let options = EscapeOptions::new(EscapeMode::Text).with_numeric_entities();
+        assert_eq!(escape_with_options("\u{85}", options), "&#x85;");
+    }
+
+    #[test]
+    fn test_numeric_entities_still_escapes_core_characters() {
+        let options = EscapeOptions::new(EscapeMode::Default).with_numeric_entities();
+        assert_eq!(
+            escape_with_options("<café>", options),
+            "&lt;caf&#xe9;&gt;"
+        );
+    }
+
+    #[test]
+    fn test_numeric_entities_disabled_by_default() {
+        assert!(!EscapeOptions::new(EscapeMode::Default).numeric_entities);
+        assert_eq!(EscapeOptions::default(), EscapeOptions::new(EscapeMode::Default));
+    }
+
+    #[cfg(feature = "memchr")]
+    mod memchr_equivalence {
+        use super::*;
+        use proptest::prelude::*;
+
+        const MODES: [EscapeMode; 4] = [
+            EscapeMode::Default,
+            EscapeMode::Text,
+            EscapeMode::Attr,
+            EscapeMode::SingleQuoteAttr,
+        ];
+
+        proptest! {
+            #[test]
+            fn scalar_and_simd_scans_agree(s in "\\PC*", start in 0usize..8) {
+                let bytes = s.as_bytes();
+                let start = start.min(bytes.len());
+
+                for mode in MODES {
+                    prop_assert_eq!(
+                        scalar_next_special(bytes, start, mode),
+                        simd_next_special(bytes, start, mode)
+                    );
+                }
+            }
+        }
+    }
 }
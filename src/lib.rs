@@ -44,6 +44,20 @@
 //! assert_eq!(unescaped, "<div>Hello & welcome!</div>");
 //! ```
 //!
+//! ## Context-aware escaping
+//!
+//! The default escaping set is safe everywhere, but escapes more than some contexts need. Use
+//! [`EscapeMode`] with [`escape_with`]/[`escape_to_with`] to escape only what the target position
+//! requires:
+//!
+//! ```rust
+//! use hescape::{escape_with, EscapeMode};
+//!
+//! assert_eq!(escape_with("Tom & Jerry", EscapeMode::Text), "Tom &amp; Jerry");
+//! assert_eq!(escape_with("O'Brien", EscapeMode::Attr), "O'Brien");
+//! assert_eq!(escape_with("O'Brien", EscapeMode::SingleQuoteAttr), "O&#39;Brien");
+//! ```
+//!
 //! ## Writing to a buffer
 //!
 //! For performance-sensitive applications, you can use [`escape_to`] and [`unescape_to`] to write directly to any
@@ -56,10 +70,77 @@
 //! escape_to(&mut buffer, "Hello <world>").unwrap();
 //! assert_eq!(buffer, "Hello &lt;world&gt;");
 //! ```
+//!
+//! ## `Display` adapters
+//!
+//! [`Escape`] and [`Unescape`] implement [`core::fmt::Display`], so they can be written directly
+//! into a formatter without materializing an intermediate [`String`]:
+//!
+//! ```rust
+//! use hescape::Escape;
+//!
+//! let input = "Tom & Jerry";
+//! assert_eq!(format!("<p>{}</p>", Escape(input)), "<p>Tom &amp; Jerry</p>");
+//! ```
+//!
+//! ## Avoiding allocations for clean input
+//!
+//! [`escape_cow`] and [`unescape_cow`] return a [`std::borrow::Cow`] that borrows the input
+//! unchanged when it requires no transformation, which avoids an allocation entirely in the
+//! common case of already-clean text:
+//!
+//! ```rust
+//! use hescape::escape_cow;
+//! use std::borrow::Cow;
+//!
+//! assert!(matches!(escape_cow("hello world"), Cow::Borrowed(_)));
+//! assert!(matches!(escape_cow("<div>"), Cow::Owned(_)));
+//! ```
+//!
+//! ## Strict unescaping
+//!
+//! [`unescape`] silently passes through malformed references, which is fine for rendering
+//! trusted data but hides corruption in content you're validating. [`try_unescape`] reports it
+//! instead, as an [`UnescapeError`] with the byte offset and kind of the first problem found:
+//!
+//! ```rust
+//! use hescape::{try_unescape, UnescapeErrorKind};
+//!
+//! let err = try_unescape("&unknown;").unwrap_err();
+//! assert_eq!(err.kind, UnescapeErrorKind::UnknownNamedReference);
+//! assert_eq!(err.offset, 0);
+//! ```
+//!
+//! ## Numeric-entity escaping
+//!
+//! By default, escaping leaves non-ASCII text as UTF-8. [`EscapeOptions::with_numeric_entities`]
+//! instead emits every non-ASCII scalar value as a hexadecimal numeric character reference, which
+//! is useful when the output must stay within ASCII (e.g. an ASCII-only transport or a legacy
+//! parser):
+//!
+//! ```rust
+//! use hescape::{escape_with_options, EscapeMode, EscapeOptions};
+//!
+//! let options = EscapeOptions::new(EscapeMode::Text).with_numeric_entities();
+//! assert_eq!(escape_with_options("café", options), "caf&#xe9;");
+//! ```
+//!
+//! ## Features
+//!
+//! - `memchr`: accelerates escaping by jumping directly to the next character that needs escaping
+//!   with SIMD-backed multi-needle search, instead of inspecting every byte. Disabled by default;
+//!   escaping falls back to an equivalent scalar scan.
+mod display;
 mod escape;
 mod unescape;
 
 pub use self::{
-    escape::{escape, escape_to},
-    unescape::{unescape, unescape_to},
+    display::{Escape, Unescape},
+    escape::{
+        escape, escape_cow, escape_to, escape_to_with, escape_to_with_options, escape_with,
+        escape_with_options, EscapeMode, EscapeOptions,
+    },
+    unescape::{
+        try_unescape, unescape, unescape_cow, unescape_to, UnescapeError, UnescapeErrorKind,
+    },
 };
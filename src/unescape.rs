@@ -0,0 +1,447 @@
+use core::fmt;
+use std::borrow::Cow;
+
+/// Unescapes a HTML string and returns the unescaped string.
+pub fn unescape(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    unescape_to(&mut output, input).unwrap();
+    output
+}
+
+/// Unescapes a HTML string, borrowing `input` unchanged if it contains no `&` (and therefore no
+/// references to unescape), and allocating an owned, unescaped string otherwise.
+pub fn unescape_cow(input: &str) -> Cow<'_, str> {
+    if !input.as_bytes().contains(&b'&') {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(unescape(input))
+    }
+}
+
+/// Unescapes a HTML string into a writer.
+pub fn unescape_to<W>(writer: &mut W, input: &str) -> fmt::Result
+where
+    W: fmt::Write + ?Sized,
+{
+    // Fast path for strings without any references
+    if !input.as_bytes().contains(&b'&') {
+        writer.write_str(input)?;
+        return Ok(());
+    }
+
+    let bytes = input.as_bytes();
+    let mut last = 0usize;
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        if bytes[i] != b'&' {
+            i += 1;
+            continue;
+        }
+
+        if let Some((replacement, consumed)) = decode_reference(&input[i..]) {
+            if last < i {
+                writer.write_str(&input[last..i])?;
+            }
+
+            match replacement {
+                Replacement::Char(c) => writer.write_char(c)?,
+                Replacement::Str(s) => writer.write_str(s)?,
+            }
+
+            i += consumed;
+            last = i;
+        } else {
+            i += 1;
+        }
+    }
+
+    if last < input.len() {
+        writer.write_str(&input[last..])?;
+    }
+
+    Ok(())
+}
+
+enum Replacement {
+    Char(char),
+    Str(&'static str),
+}
+
+/// Attempts to decode a single character reference starting at the `&` of `input`.
+///
+/// Returns the decoded replacement and the number of bytes it consumed from `input`, or `None`
+/// if `input` does not start with a well-formed reference (in which case the `&` is passed
+/// through unchanged).
+fn decode_reference(input: &str) -> Option<(Replacement, usize)> {
+    let rest = &input[1..];
+
+    if let Some(hex) = rest.strip_prefix("#x").or_else(|| rest.strip_prefix("#X")) {
+        let digits: &str = hex
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_hexdigit())
+            .map(|(idx, _)| &hex[..idx])
+            .unwrap_or(hex);
+
+        if digits.is_empty() {
+            return None;
+        }
+
+        let terminated = hex.as_bytes().get(digits.len()) == Some(&b';');
+        let consumed = 1 + 2 + digits.len() + usize::from(terminated);
+        let code_point = u32::from_str_radix(digits, 16).ok()?;
+
+        return char::from_u32(code_point)
+            .map(Replacement::Char)
+            .map(|r| (r, consumed));
+    }
+
+    if let Some(dec) = rest.strip_prefix('#') {
+        let digits: &str = dec
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_digit())
+            .map(|(idx, _)| &dec[..idx])
+            .unwrap_or(dec);
+
+        if digits.is_empty() {
+            return None;
+        }
+
+        let terminated = dec.as_bytes().get(digits.len()) == Some(&b';');
+        let consumed = 1 + 1 + digits.len() + usize::from(terminated);
+        let code_point: u32 = digits.parse().ok()?;
+
+        return char::from_u32(code_point)
+            .map(Replacement::Char)
+            .map(|r| (r, consumed));
+    }
+
+    let name_end = rest.find(|c: char| !c.is_ascii_alphanumeric())?;
+    let name = &rest[..name_end];
+
+    if rest.as_bytes().get(name_end) != Some(&b';') {
+        return None;
+    }
+
+    named_reference(name).map(|s| (Replacement::Str(s), 1 + name.len() + 1))
+}
+
+/// Looks up the replacement text for a named character reference (without the surrounding `&`
+/// and `;`).
+fn named_reference(name: &str) -> Option<&'static str> {
+    match name {
+        "amp" => Some("&"),
+        "lt" => Some("<"),
+        "gt" => Some(">"),
+        "quot" => Some("\""),
+        "apos" => Some("'"),
+        "nbsp" => Some("\u{A0}"),
+        _ => None,
+    }
+}
+
+/// What was wrong with a character reference rejected by [`try_unescape`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnescapeErrorKind {
+    /// A numeric reference (`&#...;` or `&#x...;`) was not terminated with a `;`.
+    UnterminatedReference,
+    /// A named reference (e.g. `&foo;`) does not match any known entity name.
+    UnknownNamedReference,
+    /// A numeric reference's digits could not be parsed into a code point.
+    InvalidNumericReference,
+    /// A numeric reference's code point is greater than `U+10FFFF`.
+    OutOfRangeCodepoint,
+    /// A numeric reference's code point falls in the UTF-16 surrogate range `U+D800..=U+DFFF`.
+    LoneSurrogate,
+    /// A numeric reference (`&#;` or `&#x;`) had no digits.
+    EmptyNumericReference,
+}
+
+/// An error produced by [`try_unescape`] when `input` contains a malformed character reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnescapeError {
+    /// The byte offset of the `&` that starts the malformed reference.
+    pub offset: usize,
+    /// What was wrong with the reference.
+    pub kind: UnescapeErrorKind,
+}
+
+impl fmt::Display for UnescapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.kind {
+            UnescapeErrorKind::UnterminatedReference => "unterminated numeric character reference",
+            UnescapeErrorKind::UnknownNamedReference => "unknown named character reference",
+            UnescapeErrorKind::InvalidNumericReference => "invalid numeric character reference",
+            UnescapeErrorKind::OutOfRangeCodepoint => "numeric character reference out of range",
+            UnescapeErrorKind::LoneSurrogate => "numeric character reference is a lone surrogate",
+            UnescapeErrorKind::EmptyNumericReference => "empty numeric character reference",
+        };
+
+        write!(f, "{reason} at byte offset {}", self.offset)
+    }
+}
+
+impl std::error::Error for UnescapeError {}
+
+/// Unescapes a HTML string, returning an error reporting the byte offset and kind of the first
+/// malformed character reference encountered, instead of passing it through unchanged.
+///
+/// Unlike [`unescape`], an unknown named reference or a numeric reference with no digits, a
+/// missing terminating `;`, or a code point that is out of range or a lone surrogate is treated
+/// as corrupt input rather than literal text. Use this when validating stored content rather than
+/// rendering trusted data.
+pub fn try_unescape(input: &str) -> Result<String, UnescapeError> {
+    let mut output = String::with_capacity(input.len());
+
+    let bytes = input.as_bytes();
+    let mut last = 0usize;
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        if bytes[i] != b'&' {
+            i += 1;
+            continue;
+        }
+
+        match decode_reference_strict(&input[i..]) {
+            Ok(Some((replacement, consumed))) => {
+                output.push_str(&input[last..i]);
+
+                match replacement {
+                    Replacement::Char(c) => output.push(c),
+                    Replacement::Str(s) => output.push_str(s),
+                }
+
+                i += consumed;
+                last = i;
+            }
+            Ok(None) => i += 1,
+            Err(kind) => return Err(UnescapeError { offset: i, kind }),
+        }
+    }
+
+    output.push_str(&input[last..]);
+
+    Ok(output)
+}
+
+/// Attempts to decode a single character reference starting at the `&` of `input`, rejecting
+/// malformed numeric and named references instead of passing them through.
+///
+/// Returns `Ok(None)` if `input` does not start with anything that looks like an attempted
+/// reference (a bare `&`), in which case it is passed through unchanged just as in [`unescape`].
+fn decode_reference_strict(input: &str) -> Result<Option<(Replacement, usize)>, UnescapeErrorKind> {
+    let rest = &input[1..];
+
+    if let Some(hex) = rest.strip_prefix("#x").or_else(|| rest.strip_prefix("#X")) {
+        let digits: &str = hex
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_hexdigit())
+            .map(|(idx, _)| &hex[..idx])
+            .unwrap_or(hex);
+
+        if digits.is_empty() {
+            return Err(UnescapeErrorKind::EmptyNumericReference);
+        }
+
+        if hex.as_bytes().get(digits.len()) != Some(&b';') {
+            return Err(UnescapeErrorKind::UnterminatedReference);
+        }
+
+        let code_point =
+            u32::from_str_radix(digits, 16).map_err(|_| UnescapeErrorKind::InvalidNumericReference)?;
+        let consumed = 1 + 2 + digits.len() + 1;
+
+        return decode_codepoint(code_point).map(|r| Some((r, consumed)));
+    }
+
+    if let Some(dec) = rest.strip_prefix('#') {
+        let digits: &str = dec
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_digit())
+            .map(|(idx, _)| &dec[..idx])
+            .unwrap_or(dec);
+
+        if digits.is_empty() {
+            return Err(UnescapeErrorKind::EmptyNumericReference);
+        }
+
+        if dec.as_bytes().get(digits.len()) != Some(&b';') {
+            return Err(UnescapeErrorKind::UnterminatedReference);
+        }
+
+        let code_point: u32 = digits.parse().map_err(|_| UnescapeErrorKind::InvalidNumericReference)?;
+        let consumed = 1 + 1 + digits.len() + 1;
+
+        return decode_codepoint(code_point).map(|r| Some((r, consumed)));
+    }
+
+    let Some(name_end) = rest.find(|c: char| !c.is_ascii_alphanumeric()) else {
+        return Ok(None);
+    };
+    let name = &rest[..name_end];
+
+    if name.is_empty() || rest.as_bytes().get(name_end) != Some(&b';') {
+        return Ok(None);
+    }
+
+    match named_reference(name) {
+        Some(replacement) => Ok(Some((Replacement::Str(replacement), 1 + name.len() + 1))),
+        None => Err(UnescapeErrorKind::UnknownNamedReference),
+    }
+}
+
+/// Validates a numeric reference's code point and turns it into a [`Replacement`].
+fn decode_codepoint(code_point: u32) -> Result<Replacement, UnescapeErrorKind> {
+    if code_point > 0x10FFFF {
+        return Err(UnescapeErrorKind::OutOfRangeCodepoint);
+    }
+
+    if (0xD800..=0xDFFF).contains(&code_point) {
+        return Err(UnescapeErrorKind::LoneSurrogate);
+    }
+
+    char::from_u32(code_point)
+        .map(Replacement::Char)
+        .ok_or(UnescapeErrorKind::InvalidNumericReference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_string() {
+        assert_eq!(unescape(""), "");
+    }
+
+    #[test]
+    fn test_no_references() {
+        assert_eq!(unescape("hello world"), "hello world");
+    }
+
+    #[test]
+    fn test_unescape_amp() {
+        assert_eq!(unescape("&amp;"), "&");
+    }
+
+    #[test]
+    fn test_unescape_lt_gt() {
+        assert_eq!(unescape("&lt;div&gt;"), "<div>");
+    }
+
+    #[test]
+    fn test_unescape_quot_apos() {
+        assert_eq!(unescape("&quot;&apos;"), "\"'");
+    }
+
+    #[test]
+    fn test_unescape_decimal() {
+        assert_eq!(unescape("&#60;"), "<");
+    }
+
+    #[test]
+    fn test_unescape_hex() {
+        assert_eq!(unescape("&#x3C;"), "<");
+        assert_eq!(unescape("&#X3c;"), "<");
+    }
+
+    #[test]
+    fn test_unescape_mixed_content() {
+        assert_eq!(
+            unescape("&lt;div&gt;Hello &amp; welcome!&lt;/div&gt;"),
+            "<div>Hello & welcome!</div>"
+        );
+    }
+
+    #[test]
+    fn test_unknown_reference_passthrough() {
+        assert_eq!(unescape("&unknown;"), "&unknown;");
+    }
+
+    #[test]
+    fn test_lone_ampersand() {
+        assert_eq!(unescape("a & b"), "a & b");
+    }
+
+    #[test]
+    fn test_unterminated_reference() {
+        assert_eq!(unescape("&amp"), "&amp");
+    }
+
+    #[test]
+    fn test_unescape_cow_borrows_clean_input() {
+        assert!(matches!(unescape_cow("hello world"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_unescape_cow_owns_unescaped_input() {
+        match unescape_cow("&lt;div&gt;") {
+            Cow::Owned(s) => assert_eq!(s, "<div>"),
+            Cow::Borrowed(_) => panic!("expected an owned string"),
+        }
+    }
+
+    #[test]
+    fn test_try_unescape_valid_input() {
+        assert_eq!(
+            try_unescape("&lt;div&gt;Hello &amp; welcome!&lt;/div&gt;").unwrap(),
+            "<div>Hello & welcome!</div>"
+        );
+    }
+
+    #[test]
+    fn test_try_unescape_lone_ampersand_is_not_an_error() {
+        assert_eq!(try_unescape("a & b").unwrap(), "a & b");
+    }
+
+    #[test]
+    fn test_try_unescape_unknown_named_reference() {
+        let err = try_unescape("&unknown;").unwrap_err();
+        assert_eq!(err.offset, 0);
+        assert_eq!(err.kind, UnescapeErrorKind::UnknownNamedReference);
+    }
+
+    #[test]
+    fn test_try_unescape_unterminated_numeric_reference() {
+        let err = try_unescape("&#60").unwrap_err();
+        assert_eq!(err.offset, 0);
+        assert_eq!(err.kind, UnescapeErrorKind::UnterminatedReference);
+    }
+
+    #[test]
+    fn test_try_unescape_empty_numeric_reference() {
+        let err = try_unescape("&#;").unwrap_err();
+        assert_eq!(err.kind, UnescapeErrorKind::EmptyNumericReference);
+
+        let err = try_unescape("&#x;").unwrap_err();
+        assert_eq!(err.kind, UnescapeErrorKind::EmptyNumericReference);
+    }
+
+    #[test]
+    fn test_try_unescape_out_of_range_codepoint() {
+        let err = try_unescape("&#x110000;").unwrap_err();
+        assert_eq!(err.kind, UnescapeErrorKind::OutOfRangeCodepoint);
+    }
+
+    #[test]
+    fn test_try_unescape_lone_surrogate() {
+        let err = try_unescape("&#xD800;").unwrap_err();
+        assert_eq!(err.kind, UnescapeErrorKind::LoneSurrogate);
+    }
+
+    #[test]
+    fn test_try_unescape_reports_offset_mid_string() {
+        let err = try_unescape("ok &#xD800; bad").unwrap_err();
+        assert_eq!(err.offset, 3);
+    }
+
+    #[test]
+    fn test_try_unescape_error_display() {
+        let err = try_unescape("&unknown;").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown named character reference at byte offset 0"
+        );
+    }
+}